@@ -8,10 +8,13 @@ pub mod custom;
 pub mod custom_dbus;
 pub mod disk_space;
 pub mod docker;
+pub mod fan;
 pub mod focused_window;
 pub mod github;
 pub mod hueshift;
+pub mod hwmon;
 pub mod ibus;
+pub mod json_stream;
 pub mod kdeconnect;
 pub mod keyboard_layout;
 pub mod load;
@@ -49,10 +52,13 @@ use self::custom::*;
 use self::custom_dbus::*;
 use self::disk_space::*;
 use self::docker::*;
+use self::fan::*;
 use self::focused_window::*;
 use self::github::*;
 use self::hueshift::*;
+use self::hwmon::*;
 use self::ibus::*;
+use self::json_stream::*;
 use self::kdeconnect::*;
 use self::keyboard_layout::*;
 use self::load::*;
@@ -150,12 +156,21 @@ pub trait Block {
     /// buttons (buttons are also TextWidgets). Use a vec to wrap the references to your view.
     fn view(&self) -> Vec<&dyn I3BarWidget>;
 
+    /// Text to display before the first and after the last widget of the block.
+    ///
+    /// Set through the common `prefix` and `suffix` options, so blocks don't need to implement it.
+    fn affixes(&self) -> (&str, &str) {
+        ("", "")
+    }
+
     /// Required if you don't want a static block.
     ///
     /// Use this function to update the internal state of your block, for example during
     /// periodic updates. Return the duration until your block wants to be updated next.
     /// For example, a clock could request only to be updated every 60 seconds by returning
-    /// Some(Update::Every(Duration::new(60, 0))) every time. If you return None,
+    /// Some(Update::Every(Duration::new(60, 0))) every time. The returned interval may change
+    /// between calls (e.g. to poll faster while a value is critical); the block is always
+    /// rescheduled using the latest one. If you return None,
     /// this function will not be called again automatically.
     fn update(&mut self) -> Result<Option<Update>> {
         Ok(None)
@@ -167,6 +182,12 @@ pub trait Block {
         Ok(())
     }
 
+    /// Called once before the bar exits.
+    ///
+    /// Blocks that spawn child processes or threads should stop them here, so nothing is left
+    /// running after the bar is gone.
+    fn shutdown(&mut self) {}
+
     /// Sends click events to the block.
     ///
     /// Here you can react to the user clicking your block. The I3BarEvent instance contains all
@@ -193,6 +214,9 @@ macro_rules! block {
         if let Some(overrides) = common_config.icons_format {
             $shared_config.icons_format_override(overrides);
         }
+        if let Some(ref overrides) = common_config.state_icons {
+            $shared_config.state_icons_override(overrides)?;
+        }
 
         // Extract block-specific config
         let block_config = <$block_type as ConfigBlock>::Config::deserialize($block_config)
@@ -207,6 +231,10 @@ macro_rules! block {
             name: stringify!($block_type).to_string(),
             inner: block,
             on_click: common_config.on_click,
+            on_middle_click: common_config.on_middle_click,
+            on_right_click: common_config.on_right_click,
+            prefix: common_config.prefix,
+            suffix: common_config.suffix,
         }) as Box<dyn Block>)
     }};
 }
@@ -229,6 +257,7 @@ pub fn create_block(
         "custom_dbus" => block!(CustomDBus, id, block_config, shared_config, update_request),
         "disk_space" => block!(DiskSpace, id, block_config, shared_config, update_request),
         "docker" => block!(Docker, id, block_config, shared_config, update_request), ///////
+        "fan" => block!(Fan, id, block_config, shared_config, update_request),
         "focused_window" => block!(
             FocusedWindow,
             id,
@@ -238,7 +267,9 @@ pub fn create_block(
         ),
         "github" => block!(Github, id, block_config, shared_config, update_request),
         "hueshift" => block!(Hueshift, id, block_config, shared_config, update_request),
+        "hwmon" => block!(Hwmon, id, block_config, shared_config, update_request),
         "ibus" => block!(IBus, id, block_config, shared_config, update_request),
+        "json_stream" => block!(JsonStream, id, block_config, shared_config, update_request),
         "kdeconnect" => block!(KDEConnect, id, block_config, shared_config, update_request),
         "keyboard_layout" => block!(
             KeyboardLayout,
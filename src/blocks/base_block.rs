@@ -18,6 +18,10 @@ pub(super) struct BaseBlock<T: Block> {
     pub name: String,
     pub inner: T,
     pub on_click: Option<String>,
+    pub on_middle_click: Option<String>,
+    pub on_right_click: Option<String>,
+    pub prefix: String,
+    pub suffix: String,
 }
 
 impl<T: Block> Block for BaseBlock<T> {
@@ -25,6 +29,10 @@ impl<T: Block> Block for BaseBlock<T> {
         self.inner.id()
     }
 
+    fn affixes(&self) -> (&str, &str) {
+        (&self.prefix, &self.suffix)
+    }
+
     fn view(&self) -> Vec<&dyn I3BarWidget> {
         self.inner.view()
     }
@@ -37,13 +45,23 @@ impl<T: Block> Block for BaseBlock<T> {
         self.inner.signal(signal)
     }
 
+    fn shutdown(&mut self) {
+        self.inner.shutdown()
+    }
+
     fn click(&mut self, e: &I3BarEvent) -> Result<()> {
-        match &self.on_click {
+        // A command configured for the clicked button takes precedence over the block's own
+        // click handling, other buttons are still passed on to the block
+        let cmd = match e.button {
+            MouseButton::Left => &self.on_click,
+            MouseButton::Middle => &self.on_middle_click,
+            MouseButton::Right => &self.on_right_click,
+            _ => &None,
+        };
+        match cmd {
             Some(cmd) => {
-                if let MouseButton::Left = e.button {
-                    spawn_child_async("sh", &["-c", &cmd])
-                        .block_error(&self.name, "could not spawn child")?;
-                }
+                spawn_child_async("sh", &["-c", cmd])
+                    .block_error(&self.name, "could not spawn child")?;
                 Ok(())
             }
             None => self.inner.click(e),
@@ -53,15 +71,41 @@ impl<T: Block> Block for BaseBlock<T> {
 
 #[derive(Deserialize, Debug, Default, Clone)]
 pub(super) struct BaseBlockConfig {
-    /// Command to execute when the button is clicked
+    /// Command to execute when the block is left-clicked
     pub on_click: Option<String>,
 
+    /// Command to execute when the block is middle-clicked
+    pub on_middle_click: Option<String>,
+
+    /// Command to execute when the block is right-clicked
+    pub on_right_click: Option<String>,
+
     pub theme_overrides: Option<HashMap<String, String>>,
     pub icons_format: Option<String>,
+
+    /// Icons to display instead of the block's own icon while it is in a given state
+    pub state_icons: Option<HashMap<String, String>>,
+
+    /// Text displayed before the block's first widget
+    #[serde(default)]
+    pub prefix: String,
+
+    /// Text displayed after the block's last widget
+    #[serde(default)]
+    pub suffix: String,
 }
 
 impl BaseBlockConfig {
-    const FIELDS: &'static [&'static str] = &["on_click", "theme_overrides", "icons_format"];
+    const FIELDS: &'static [&'static str] = &[
+        "on_click",
+        "on_middle_click",
+        "on_right_click",
+        "theme_overrides",
+        "icons_format",
+        "state_icons",
+        "prefix",
+        "suffix",
+    ];
 
     // FIXME: this function is to paper over https://github.com/serde-rs/serde/issues/1957
     pub(super) fn extract(config: &mut Value) -> Value {
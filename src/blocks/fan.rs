@@ -0,0 +1,212 @@
+use std::cmp::Ordering;
+use std::path::Path;
+use std::time::Duration;
+
+use crossbeam_channel::Sender;
+use serde_derive::Deserialize;
+
+use crate::blocks::hwmon::{read_sensors, Sensor, HWMON_PATH};
+use crate::blocks::{Block, ConfigBlock, Update};
+use crate::config::SharedConfig;
+use crate::de::deserialize_duration;
+use crate::errors::*;
+use crate::scheduler::Task;
+use crate::util::FormatTemplate;
+use crate::widgets::{text::TextWidget, I3BarWidget, State};
+
+pub struct Fan {
+    id: usize,
+    text: TextWidget,
+    update_interval: Duration,
+    format: FormatTemplate,
+    chip: Option<String>,
+    label: Option<String>,
+    warning: f64,
+    critical_on_stall: bool,
+}
+
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct FanConfig {
+    /// Update interval in seconds
+    #[serde(
+        default = "FanConfig::default_interval",
+        deserialize_with = "deserialize_duration"
+    )]
+    pub interval: Duration,
+
+    /// Format override
+    #[serde(default = "FanConfig::default_format")]
+    pub format: String,
+
+    /// Only use fans of the chip with this name (as found in `/sys/class/hwmon/*/name`)
+    #[serde(default)]
+    pub chip: Option<String>,
+
+    /// Only use fans whose label contains this string
+    #[serde(default)]
+    pub label: Option<String>,
+
+    /// Percentage of the maximum speed from which state is set to warning
+    #[serde(default = "FanConfig::default_warning")]
+    pub warning: f64,
+
+    /// Whether a stalled (0 RPM) fan sets state to critical
+    #[serde(default = "FanConfig::default_critical_on_stall")]
+    pub critical_on_stall: bool,
+}
+
+impl FanConfig {
+    fn default_interval() -> Duration {
+        Duration::from_secs(5)
+    }
+
+    fn default_format() -> String {
+        "{rpm} RPM".to_owned()
+    }
+
+    fn default_warning() -> f64 {
+        90.
+    }
+
+    fn default_critical_on_stall() -> bool {
+        true
+    }
+}
+
+fn slowest<'a>(fans: impl Iterator<Item = &'a Sensor>) -> Option<&'a Sensor> {
+    fans.min_by(|a, b| a.value.partial_cmp(&b.value).unwrap_or(Ordering::Equal))
+}
+
+/// Picks the slowest fan, so a stalled fan is never hidden. Unless fans were picked by label,
+/// fans at 0 RPM are skipped while others spin, since most chips report unused headers as 0 RPM.
+fn select_fan(fans: &[Sensor], explicit: bool) -> Option<&Sensor> {
+    if explicit {
+        return slowest(fans.iter());
+    }
+    slowest(fans.iter().filter(|fan| fan.value > 0.)).or_else(|| fans.first())
+}
+
+/// Speed as a percentage of the maximum, for fans that report one
+fn percentage(fan: &Sensor) -> Option<f64> {
+    fan.max
+        .filter(|&max| max > 0.)
+        .map(|max| fan.value / max * 100.)
+}
+
+fn fan_state(fan: &Sensor, warning: f64, critical_on_stall: bool) -> State {
+    match percentage(fan) {
+        _ if critical_on_stall && fan.value == 0. => State::Critical,
+        Some(p) if p >= warning => State::Warning,
+        _ => State::Idle,
+    }
+}
+
+impl ConfigBlock for Fan {
+    type Config = FanConfig;
+
+    fn new(
+        id: usize,
+        block_config: Self::Config,
+        shared_config: SharedConfig,
+        _tx_update_request: Sender<Task>,
+    ) -> Result<Self> {
+        Ok(Fan {
+            id,
+            update_interval: block_config.interval,
+            text: TextWidget::new(id, 0, shared_config).with_icon("fan"),
+            format: FormatTemplate::from_string(&block_config.format)
+                .block_error("fan", "Invalid format specified for fan")?
+                .with_keys("fan", &["{rpm}", "{rpm_percentage}", "{label}"])?,
+            chip: block_config.chip,
+            label: block_config.label,
+            warning: block_config.warning,
+            critical_on_stall: block_config.critical_on_stall,
+        })
+    }
+}
+
+impl Block for Fan {
+    fn update(&mut self) -> Result<Option<Update>> {
+        let fans = read_sensors(
+            Path::new(HWMON_PATH),
+            "fan",
+            self.chip.as_deref(),
+            self.label.as_deref(),
+        )?;
+
+        let fan =
+            select_fan(&fans, self.label.is_some()).block_error("fan", "no matching fan found")?;
+
+        let values = map!("{rpm}" => format!("{:.0}", fan.value),
+                          "{rpm_percentage}" => percentage(fan)
+                              .map(|p| format!("{:.0}", p))
+                              .unwrap_or_else(|| "?".to_string()),
+                          "{label}" => fan.label.clone());
+        self.text.set_text(self.format.render_static_str(&values)?);
+
+        self.text
+            .set_state(fan_state(fan, self.warning, self.critical_on_stall));
+
+        Ok(Some(self.update_interval.into()))
+    }
+
+    fn view(&self) -> Vec<&dyn I3BarWidget> {
+        vec![&self.text]
+    }
+
+    fn id(&self) -> usize {
+        self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blocks::fan::{fan_state, select_fan};
+    use crate::blocks::hwmon::Sensor;
+    use crate::widgets::State;
+
+    fn fan(label: &str, value: f64, max: Option<f64>) -> Sensor {
+        Sensor {
+            label: label.to_string(),
+            value,
+            max,
+        }
+    }
+
+    #[test]
+    fn test_select_fan() {
+        let fans = vec![
+            fan("fan1", 1200., None),
+            fan("fan2", 0., None),
+            fan("fan3", 800., None),
+        ];
+        // Unused headers are skipped...
+        assert_eq!(select_fan(&fans, false).unwrap().label, "fan3");
+        // ...unless fans were picked by label
+        assert_eq!(select_fan(&fans, true).unwrap().label, "fan2");
+
+        let stopped = vec![fan("fan1", 0., None), fan("fan2", 0., None)];
+        assert_eq!(select_fan(&stopped, false).unwrap().label, "fan1");
+        assert!(select_fan(&[], false).is_none());
+    }
+
+    #[test]
+    fn test_fan_state() {
+        assert_eq!(
+            fan_state(&fan("fan1", 0., None), 90., true),
+            State::Critical
+        );
+        assert_eq!(fan_state(&fan("fan1", 0., None), 90., false), State::Idle);
+        assert_eq!(
+            fan_state(&fan("fan1", 2800., Some(3000.)), 90., true),
+            State::Warning
+        );
+        assert_eq!(
+            fan_state(&fan("fan1", 1500., Some(3000.)), 90., true),
+            State::Idle
+        );
+        // Without a maximum, there is no percentage to warn about
+        assert_eq!(fan_state(&fan("fan1", 9000., None), 90., true), State::Idle);
+    }
+}
@@ -0,0 +1,362 @@
+use std::cmp::Ordering;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use crossbeam_channel::Sender;
+use serde_derive::Deserialize;
+
+use crate::blocks::temperature::TemperatureScale;
+use crate::blocks::{Block, ConfigBlock, Update};
+use crate::config::SharedConfig;
+use crate::de::deserialize_duration;
+use crate::errors::*;
+use crate::scheduler::Task;
+use crate::util::FormatTemplate;
+use crate::widgets::{text::TextWidget, I3BarWidget, State};
+
+pub(super) const HWMON_PATH: &str = "/sys/class/hwmon";
+
+pub struct Hwmon {
+    id: usize,
+    text: TextWidget,
+    update_interval: Duration,
+    format: FormatTemplate,
+    chip: Option<String>,
+    label: Option<String>,
+    scale: TemperatureScale,
+    info: i64,
+    warning: i64,
+    critical: i64,
+    hysteresis: i64,
+    /// Number of thresholds currently reached, see `threshold_level`
+    level: usize,
+}
+
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct HwmonConfig {
+    /// Update interval in seconds
+    #[serde(
+        default = "HwmonConfig::default_interval",
+        deserialize_with = "deserialize_duration"
+    )]
+    pub interval: Duration,
+
+    /// Format override
+    #[serde(default = "HwmonConfig::default_format")]
+    pub format: String,
+
+    /// Only use sensors of the chip with this name (as found in `/sys/class/hwmon/*/name`)
+    #[serde(default)]
+    pub chip: Option<String>,
+
+    /// Only use sensors whose label contains this string
+    #[serde(default)]
+    pub label: Option<String>,
+
+    /// The temperature scale to use for display and thresholds
+    #[serde(default)]
+    pub scale: TemperatureScale,
+
+    /// Minimum temperature to set state to info
+    #[serde(default)]
+    pub info: Option<i64>,
+
+    /// Minimum temperature to set state to warning
+    #[serde(default)]
+    pub warning: Option<i64>,
+
+    /// Minimum temperature to set state to critical
+    #[serde(default)]
+    pub critical: Option<i64>,
+
+    /// How many degrees the temperature has to drop below a threshold before the state goes down
+    #[serde(default)]
+    pub hysteresis: i64,
+}
+
+impl HwmonConfig {
+    fn default_interval() -> Duration {
+        Duration::from_secs(5)
+    }
+
+    fn default_format() -> String {
+        "{temp}°".to_owned()
+    }
+}
+
+/// Returns how many of the ascending `thresholds` are reached by `value`. Once reached, a
+/// threshold only counts as left again when `value` drops `hysteresis` below it, so that a value
+/// hovering around a threshold doesn't make the state flicker.
+fn threshold_level(thresholds: &[i64], value: i64, previous: usize, hysteresis: i64) -> usize {
+    let reached = |margin: i64| thresholds.iter().filter(|&&t| value >= t - margin).count();
+    reached(0).max(previous.min(reached(hysteresis)))
+}
+
+#[derive(Debug, PartialEq)]
+pub(super) struct Sensor {
+    pub label: String,
+    /// Raw value of the `*_input` file
+    pub value: f64,
+    /// Raw value of the `*_max` file, if the chip reports one
+    pub max: Option<f64>,
+}
+
+/// Reads all `<kind>*_input` sensors below `root` (e.g. `temp1_input` or `fan2_input`), keeping
+/// those matching the chip name and label filters.
+pub(super) fn read_sensors(
+    root: &Path,
+    kind: &str,
+    chip: Option<&str>,
+    label: Option<&str>,
+) -> Result<Vec<Sensor>> {
+    let mut sensors = Vec::new();
+
+    let chips = fs::read_dir(root).block_error(
+        "hwmon",
+        &format!("failed to read {}", root.to_string_lossy()),
+    )?;
+    for chip_dir in chips.flatten() {
+        let chip_path = chip_dir.path();
+        if let Some(chip) = chip {
+            match fs::read_to_string(chip_path.join("name")) {
+                Ok(name) if name.trim() == chip => {}
+                _ => continue,
+            }
+        }
+
+        let entries = match fs::read_dir(&chip_path) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+        for entry in entries.flatten() {
+            let file_name = entry.file_name();
+            let file_name = file_name.to_string_lossy();
+            let input = match file_name
+                .strip_prefix(kind)
+                .and_then(|rest| rest.strip_suffix("_input"))
+            {
+                Some(input) => input,
+                None => continue,
+            };
+
+            let sensor_label =
+                fs::read_to_string(chip_path.join(format!("{}{}_label", kind, input)))
+                    .map(|l| l.trim().to_string())
+                    .unwrap_or_else(|_| format!("{}{}", kind, input));
+            if let Some(label) = label {
+                if !sensor_label.contains(label) {
+                    continue;
+                }
+            }
+
+            // Some drivers expose inputs that can't be read (e.g. a disconnected fan header),
+            // skip those instead of failing the whole block
+            let value = match fs::read_to_string(entry.path())
+                .ok()
+                .and_then(|value| value.trim().parse::<f64>().ok())
+            {
+                Some(value) => value,
+                None => continue,
+            };
+            let max = fs::read_to_string(chip_path.join(format!("{}{}_max", kind, input)))
+                .ok()
+                .and_then(|max| max.trim().parse::<f64>().ok());
+            sensors.push(Sensor {
+                label: sensor_label,
+                value,
+                max,
+            });
+        }
+    }
+
+    Ok(sensors)
+}
+
+impl ConfigBlock for Hwmon {
+    type Config = HwmonConfig;
+
+    fn new(
+        id: usize,
+        block_config: Self::Config,
+        shared_config: SharedConfig,
+        _tx_update_request: Sender<Task>,
+    ) -> Result<Self> {
+        let scale = block_config.scale;
+        Ok(Hwmon {
+            id,
+            update_interval: block_config.interval,
+            text: TextWidget::new(id, 0, shared_config).with_icon("thermometer"),
+            format: FormatTemplate::from_string(&block_config.format)
+                .block_error("hwmon", "Invalid format specified for hwmon")?
+                .with_keys("hwmon", &["{temp}", "{unit}", "{label}"])?,
+            chip: block_config.chip,
+            label: block_config.label,
+            scale,
+            info: block_config.info.unwrap_or(match scale {
+                TemperatureScale::Celsius => 45,
+                TemperatureScale::Fahrenheit => 113,
+            }),
+            warning: block_config.warning.unwrap_or(match scale {
+                TemperatureScale::Celsius => 60,
+                TemperatureScale::Fahrenheit => 140,
+            }),
+            critical: block_config.critical.unwrap_or(match scale {
+                TemperatureScale::Celsius => 80,
+                TemperatureScale::Fahrenheit => 176,
+            }),
+            hysteresis: block_config.hysteresis,
+            level: 0,
+        })
+    }
+}
+
+impl Block for Hwmon {
+    fn update(&mut self) -> Result<Option<Update>> {
+        let sensors = read_sensors(
+            Path::new(HWMON_PATH),
+            "temp",
+            self.chip.as_deref(),
+            self.label.as_deref(),
+        )?;
+
+        // Show the hottest of the matching sensors
+        let hottest = sensors
+            .iter()
+            .max_by(|a, b| a.value.partial_cmp(&b.value).unwrap_or(Ordering::Equal))
+            .block_error("hwmon", "no matching temperature sensor found")?;
+        // Temperatures are reported in millidegrees Celsius
+        let temp = self.scale.convert(hottest.value / 1000.).round() as i64;
+
+        let values = map!("{temp}" => temp.to_string(),
+                          "{unit}" => self.scale.unit().to_string(),
+                          "{label}" => hottest.label.clone());
+        self.text.set_text(self.format.render_static_str(&values)?);
+
+        self.level = threshold_level(
+            &[self.info, self.warning, self.critical],
+            temp,
+            self.level,
+            self.hysteresis,
+        );
+        self.text.set_state(match self.level {
+            0 => State::Idle,
+            1 => State::Info,
+            2 => State::Warning,
+            _ => State::Critical,
+        });
+
+        Ok(Some(self.update_interval.into()))
+    }
+
+    fn view(&self) -> Vec<&dyn I3BarWidget> {
+        vec![&self.text]
+    }
+
+    fn id(&self) -> usize {
+        self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blocks::hwmon::{read_sensors, threshold_level, Sensor};
+    use assert_fs::prelude::{FileWriteStr, PathChild};
+    use assert_fs::TempDir;
+
+    fn hwmon_tree() -> TempDir {
+        let root = TempDir::new().unwrap();
+        let files = [
+            ("hwmon0/name", "k10temp\n"),
+            ("hwmon0/temp1_input", "45125\n"),
+            ("hwmon0/temp1_label", "Tctl\n"),
+            ("hwmon0/temp1_max", "70000\n"),
+            ("hwmon0/temp3_input", "38000\n"),
+            ("hwmon0/temp3_label", "Tccd1\n"),
+            ("hwmon1/name", "amdgpu\n"),
+            ("hwmon1/temp1_input", "52000\n"),
+            ("hwmon1/fan1_input", "1200\n"),
+            ("hwmon1/fan1_max", "3000\n"),
+        ];
+        for (path, content) in files.iter() {
+            root.child(path).write_str(content).unwrap();
+        }
+        root
+    }
+
+    #[test]
+    fn test_read_sensors_by_chip() {
+        let root = hwmon_tree();
+        let sensors = read_sensors(root.path(), "temp", Some("amdgpu"), None).unwrap();
+        assert_eq!(
+            sensors,
+            vec![Sensor {
+                label: "temp1".to_string(),
+                value: 52000.,
+                max: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_read_sensors_by_label() {
+        let root = hwmon_tree();
+        let sensors = read_sensors(root.path(), "temp", Some("k10temp"), Some("ccd")).unwrap();
+        assert_eq!(
+            sensors,
+            vec![Sensor {
+                label: "Tccd1".to_string(),
+                value: 38000.,
+                max: None,
+            }]
+        );
+        let sensors = read_sensors(root.path(), "temp", None, None).unwrap();
+        assert_eq!(sensors.len(), 3);
+    }
+
+    #[test]
+    fn test_read_sensors_max() {
+        let root = hwmon_tree();
+        let sensors = read_sensors(root.path(), "temp", None, Some("Tctl")).unwrap();
+        assert_eq!(sensors[0].max, Some(70000.));
+    }
+
+    #[test]
+    fn test_read_sensors_fan() {
+        let root = hwmon_tree();
+        let sensors = read_sensors(root.path(), "fan", None, None).unwrap();
+        assert_eq!(
+            sensors,
+            vec![Sensor {
+                label: "fan1".to_string(),
+                value: 1200.,
+                max: Some(3000.),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_read_sensors_skips_unreadable() {
+        let root = hwmon_tree();
+        root.child("hwmon1/fan2_input").write_str("N/A\n").unwrap();
+        let sensors = read_sensors(root.path(), "fan", None, None).unwrap();
+        assert_eq!(sensors.len(), 1);
+        assert_eq!(sensors[0].label, "fan1");
+    }
+
+    #[test]
+    fn test_threshold_level_hysteresis() {
+        let thresholds = [45, 60, 80];
+        assert_eq!(threshold_level(&thresholds, 59, 0, 3), 1);
+        assert_eq!(threshold_level(&thresholds, 60, 1, 3), 2);
+        // Hovering just below the threshold keeps the state
+        assert_eq!(threshold_level(&thresholds, 58, 2, 3), 2);
+        assert_eq!(threshold_level(&thresholds, 57, 2, 3), 2);
+        assert_eq!(threshold_level(&thresholds, 56, 2, 3), 1);
+        // A sudden drop can skip several levels
+        assert_eq!(threshold_level(&thresholds, 30, 3, 3), 0);
+        // Without hysteresis, the state follows the value directly
+        assert_eq!(threshold_level(&thresholds, 59, 2, 0), 1);
+    }
+}
@@ -0,0 +1,403 @@
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read};
+use std::os::unix::process::CommandExt;
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crossbeam_channel::Sender;
+use nix::sys::signal::{killpg, Signal};
+use nix::unistd::{setpgid, Pid};
+use serde_derive::Deserialize;
+use serde_json::Value;
+
+use crate::blocks::{Block, ConfigBlock, Update};
+use crate::config::SharedConfig;
+use crate::errors::*;
+use crate::scheduler::Task;
+use crate::util::{escape_pango_text, FormatTemplate};
+use crate::widgets::{text::TextWidget, I3BarWidget, State};
+
+/// How long the command gets to exit after SIGTERM before it is killed
+const SHUTDOWN_GRACE: Duration = Duration::from_millis(500);
+
+pub struct JsonStream {
+    id: usize,
+    text: TextWidget,
+    format: FormatTemplate,
+    /// Latest values, keyed by placeholder (e.g. `{gpu}`); `None` until the first line arrives
+    values: Arc<Mutex<Option<HashMap<String, String>>>>,
+    /// First line the command printed to stderr
+    error: Arc<Mutex<Option<String>>>,
+    /// Exit status of the command, once it stopped
+    status: Arc<Mutex<Option<ExitStatus>>>,
+    /// Whether the block has rendered any values yet
+    received: bool,
+    /// Whether to show the widget before any values arrive, i.e. `waiting_text` or an error
+    show_waiting: bool,
+    state_field: Option<String>,
+    info: Option<f64>,
+    warning: Option<f64>,
+    critical: Option<f64>,
+    /// Process group of the command
+    pid: Pid,
+    readers: Vec<JoinHandle<()>>,
+}
+
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct JsonStreamConfig {
+    /// Shell command printing one JSON object per line
+    pub command: String,
+
+    /// Format string, using the names of `fields` as placeholders
+    pub format: String,
+
+    /// Map of placeholder names to JSON pointers (e.g. `gpu = "/gpu/percent"`)
+    #[serde(default)]
+    pub fields: HashMap<String, String>,
+
+    /// Name of the numeric field that determines the state of the block
+    #[serde(default)]
+    pub state_field: Option<String>,
+
+    /// Minimum value of `state_field` to set state to info
+    #[serde(default)]
+    pub info: Option<f64>,
+
+    /// Minimum value of `state_field` to set state to warning
+    #[serde(default)]
+    pub warning: Option<f64>,
+
+    /// Minimum value of `state_field` to set state to critical
+    #[serde(default)]
+    pub critical: Option<f64>,
+
+    /// Text shown until the command prints its first line
+    #[serde(default = "JsonStreamConfig::default_waiting_text")]
+    pub waiting_text: String,
+}
+
+impl JsonStreamConfig {
+    fn default_waiting_text() -> String {
+        "…".to_owned()
+    }
+}
+
+/// Looks up every `(placeholder, pointer)` pair in a line of JSON. Fields missing from the line
+/// are rendered as `?`, values are escaped for pango.
+fn extract_fields(line: &str, fields: &[(String, String)]) -> Option<HashMap<String, String>> {
+    let json: Value = serde_json::from_str(line).ok()?;
+    Some(
+        fields
+            .iter()
+            .map(|(placeholder, pointer)| {
+                let value = match json.pointer(pointer) {
+                    Some(Value::String(s)) => s.clone(),
+                    Some(Value::Null) | None => "?".to_string(),
+                    Some(value) => value.to_string(),
+                };
+                (placeholder.clone(), escape_pango_text(value))
+            })
+            .collect(),
+    )
+}
+
+/// Like `BufRead::lines()`, but invalid UTF-8 is replaced instead of ending the iterator, so a
+/// stray byte doesn't close the pipe on the command.
+fn lossy_lines(reader: impl Read) -> impl Iterator<Item = String> {
+    let mut reader = BufReader::new(reader);
+    std::iter::from_fn(move || {
+        let mut line = Vec::new();
+        match reader.read_until(b'\n', &mut line) {
+            Ok(0) | Err(_) => None,
+            Ok(_) => {
+                if line.ends_with(b"\n") {
+                    line.pop();
+                }
+                Some(String::from_utf8_lossy(&line).into_owned())
+            }
+        }
+    })
+}
+
+impl ConfigBlock for JsonStream {
+    type Config = JsonStreamConfig;
+
+    fn new(
+        id: usize,
+        block_config: Self::Config,
+        shared_config: SharedConfig,
+        tx_update_request: Sender<Task>,
+    ) -> Result<Self> {
+        let fields: Vec<(String, String)> = block_config
+            .fields
+            .into_iter()
+            .map(|(name, pointer)| (format!("{{{}}}", name), pointer))
+            .collect();
+        let placeholders: Vec<&str> = fields.iter().map(|(p, _)| p.as_str()).collect();
+        let format = FormatTemplate::from_string(&block_config.format)
+            .block_error("json_stream", "Invalid format specified for json_stream")?
+            .with_keys("json_stream", &placeholders)?;
+
+        let state_field = block_config.state_field.map(|name| format!("{{{}}}", name));
+        if let Some(ref state_field) = state_field {
+            if !placeholders.contains(&state_field.as_str()) {
+                return Err(block_error(
+                    "json_stream",
+                    &format!("state_field {} is not one of the fields", state_field),
+                ));
+            }
+        }
+
+        let mut command = Command::new("sh");
+        command
+            .args(["-c", &block_config.command])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        // Run the command in its own process group, so that shutdown() also stops anything the
+        // shell started
+        unsafe {
+            command.pre_exec(|| {
+                setpgid(Pid::from_raw(0), Pid::from_raw(0))
+                    .map_err(|_| std::io::Error::last_os_error())
+            });
+        }
+        let mut child = command
+            .spawn()
+            .block_error("json_stream", "failed to start command")?;
+        let stdout = child
+            .stdout
+            .take()
+            .block_error("json_stream", "failed to pipe command output")?;
+        let stderr = child
+            .stderr
+            .take()
+            .block_error("json_stream", "failed to pipe command errors")?;
+
+        let values = Arc::new(Mutex::new(None));
+        let thread_values = values.clone();
+        let pid = Pid::from_raw(child.id() as i32);
+        let status = Arc::new(Mutex::new(None));
+        let thread_status = status.clone();
+        let tx_error_update = tx_update_request.clone();
+        let reader = thread::Builder::new()
+            .name("json_stream".into())
+            .spawn(move || {
+                for line in lossy_lines(stdout) {
+                    // Skip anything that isn't a JSON object, e.g. a banner
+                    if let Some(new_values) = extract_fields(&line, &fields) {
+                        *thread_values.lock().unwrap() = Some(new_values);
+                        tx_update_request
+                            .send(Task {
+                                id,
+                                update_time: Instant::now(),
+                            })
+                            .unwrap();
+                    }
+                }
+                // The command closed its output, wait for it so that it doesn't linger as a zombie
+                if let Ok(status) = child.wait() {
+                    *thread_status.lock().unwrap() = Some(status);
+                }
+                let _ = tx_update_request.send(Task {
+                    id,
+                    update_time: Instant::now(),
+                });
+            })
+            .unwrap();
+
+        let error = Arc::new(Mutex::new(None));
+        let thread_error = error.clone();
+        let error_reader = thread::Builder::new()
+            .name("json_stream_stderr".into())
+            .spawn(move || {
+                let mut lines = lossy_lines(stderr);
+                let first_line = lines.by_ref().find(|line| !line.trim().is_empty());
+                if let Some(line) = first_line {
+                    *thread_error.lock().unwrap() = Some(line.trim().to_string());
+                    tx_error_update
+                        .send(Task {
+                            id,
+                            update_time: Instant::now(),
+                        })
+                        .unwrap();
+                }
+                // Keep draining stderr until EOF: closing the pipe would kill the command with
+                // SIGPIPE the next time it writes a warning
+                lines.for_each(drop);
+            })
+            .unwrap();
+
+        Ok(JsonStream {
+            id,
+            text: TextWidget::new(id, 0, shared_config).with_text(&block_config.waiting_text),
+            format,
+            values,
+            error,
+            status,
+            received: false,
+            show_waiting: !block_config.waiting_text.is_empty(),
+            state_field,
+            info: block_config.info,
+            warning: block_config.warning,
+            critical: block_config.critical,
+            pid,
+            readers: vec![reader, error_reader],
+        })
+    }
+}
+
+impl JsonStream {
+    /// Waits up to `timeout` for the reader threads to end, returning whether they did
+    fn wait_for_readers(&self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if self.readers.iter().all(|reader| reader.is_finished()) {
+                return true;
+            }
+            if Instant::now() >= deadline {
+                return false;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+}
+
+impl Block for JsonStream {
+    fn update(&mut self) -> Result<Option<Update>> {
+        let status = *self
+            .status
+            .lock()
+            .block_error("json_stream", "mutex poisoned")?;
+        if let Some(status) = status {
+            // Show why the command stopped instead of its last, stale values
+            let message = match *self
+                .error
+                .lock()
+                .block_error("json_stream", "mutex poisoned")?
+            {
+                Some(ref error) => error.clone(),
+                None => format!("command exited ({})", status),
+            };
+            self.show_waiting = true;
+            self.text.set_text(escape_pango_text(message));
+            self.text.set_state(State::Critical);
+            return Ok(None);
+        }
+
+        let values = self
+            .values
+            .lock()
+            .block_error("json_stream", "mutex poisoned")?;
+        let values = match *values {
+            Some(ref values) => values,
+            None => {
+                // Until there is output, show what the command complained about, if anything
+                if let Some(ref error) = *self
+                    .error
+                    .lock()
+                    .block_error("json_stream", "mutex poisoned")?
+                {
+                    self.show_waiting = true;
+                    self.text.set_text(escape_pango_text(error.clone()));
+                    self.text.set_state(State::Critical);
+                }
+                return Ok(None);
+            }
+        };
+
+        self.received = true;
+        let placeholders: HashMap<&str, &String> =
+            values.iter().map(|(k, v)| (k.as_str(), v)).collect();
+        self.text
+            .set_text(self.format.render_static_str(&placeholders)?);
+
+        let state_value = self
+            .state_field
+            .as_ref()
+            .and_then(|field| values.get(field))
+            .and_then(|value| value.parse::<f64>().ok());
+        let reached = |threshold: Option<f64>| match (state_value, threshold) {
+            (Some(value), Some(threshold)) => value >= threshold,
+            _ => false,
+        };
+        self.text.set_state(if reached(self.critical) {
+            State::Critical
+        } else if reached(self.warning) {
+            State::Warning
+        } else if reached(self.info) {
+            State::Info
+        } else {
+            State::Idle
+        });
+
+        Ok(None)
+    }
+
+    fn shutdown(&mut self) {
+        // Killing the command closes its output, which ends the reader threads. The stdout
+        // reader also reaps the command.
+        let _ = killpg(self.pid, Signal::SIGTERM);
+        if !self.wait_for_readers(SHUTDOWN_GRACE) {
+            // The command ignores SIGTERM
+            let _ = killpg(self.pid, Signal::SIGKILL);
+            // A process that left the group (e.g. via setsid) may still hold the pipes open, so
+            // give up on the readers rather than hanging the bar
+            self.wait_for_readers(SHUTDOWN_GRACE);
+        }
+        for reader in self.readers.drain(..) {
+            if reader.is_finished() {
+                let _ = reader.join();
+            }
+        }
+    }
+
+    fn view(&self) -> Vec<&dyn I3BarWidget> {
+        if self.received || self.show_waiting {
+            vec![&self.text]
+        } else {
+            vec![]
+        }
+    }
+
+    fn id(&self) -> usize {
+        self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blocks::json_stream::{extract_fields, lossy_lines};
+
+    #[test]
+    fn test_extract_fields() {
+        let fields = vec![
+            ("{gpu}".to_string(), "/gpu/percent".to_string()),
+            ("{name}".to_string(), "/name".to_string()),
+            ("{fan}".to_string(), "/fans/0".to_string()),
+            ("{missing}".to_string(), "/nope".to_string()),
+            ("{vendor}".to_string(), "/vendor".to_string()),
+        ];
+        let values = extract_fields(
+            r#"{"gpu": {"percent": 42.5}, "name": "card0", "fans": [1200, 900], "vendor": "AMD <ATI> & co"}"#,
+            &fields,
+        )
+        .unwrap();
+        assert_eq!(values["{gpu}"], "42.5");
+        assert_eq!(values["{name}"], "card0");
+        assert_eq!(values["{fan}"], "1200");
+        assert_eq!(values["{missing}"], "?");
+        assert_eq!(values["{vendor}"], "AMD &lt;ATI&gt; &amp; co");
+
+        assert!(extract_fields("radeontop 1.3, running", &fields).is_none());
+    }
+
+    #[test]
+    fn test_lossy_lines() {
+        let output: &[u8] = b"{\"a\": 1}\nbad \xff byte\n{\"a\": 2}";
+        let lines: Vec<String> = lossy_lines(output).collect();
+        assert_eq!(lines, vec!["{\"a\": 1}", "bad \u{fffd} byte", "{\"a\": 2}"]);
+    }
+}
@@ -100,7 +100,8 @@ impl ConfigBlock for Load {
             minimum_warning: block_config.warning,
             minimum_critical: block_config.critical,
             format: FormatTemplate::from_string(&block_config.format)
-                .block_error("load", "Invalid format specified for load")?,
+                .block_error("load", "Invalid format specified for load")?
+                .with_keys("load", &["{1m}", "{5m}", "{15m}"])?,
             text,
         })
     }
@@ -18,7 +18,8 @@ use crate::errors::*;
 use crate::input::{I3BarEvent, MouseButton};
 use crate::scheduler::Task;
 use crate::util::{
-    escape_pango_text, format_number, format_percent_bar, format_vec_to_bar_graph, FormatTemplate,
+    escape_pango_text, format_number, format_percent_bar, format_vec_to_bar_graph, Base,
+    FormatTemplate,
 };
 use crate::widgets::{text::TextWidget, I3BarWidget, Spacing};
 
@@ -359,6 +360,7 @@ pub struct Net {
     rx_bytes: u64,
     use_bits: bool,
     speed_min_unit: Unit,
+    speed_max_unit: Option<Unit>,
     speed_digits: usize,
     active: bool,
     exists: bool,
@@ -368,7 +370,7 @@ pub struct Net {
     shared_config: SharedConfig,
 }
 
-#[derive(Copy, Clone, Debug, Deserialize)]
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Deserialize)]
 pub enum Unit {
     B,
     K,
@@ -431,6 +433,9 @@ pub struct NetConfig {
     /// Minimum unit to display for throughput indicators.
     #[serde(default = "NetConfig::default_speed_min_unit")]
     pub speed_min_unit: Unit,
+
+    /// Maximum unit to display for throughput indicators.
+    pub speed_max_unit: Option<Unit>,
 }
 
 impl NetConfig {
@@ -493,6 +498,21 @@ impl ConfigBlock for Net {
         let wireless = device.is_wireless();
         let vpn = device.is_vpn();
 
+        if let Some(max_unit) = block_config.speed_max_unit {
+            if max_unit < block_config.speed_min_unit {
+                return Err(ConfigurationError(
+                    "net".to_string(),
+                    (
+                        format!(
+                            "speed_max_unit ({}) is smaller than speed_min_unit ({})",
+                            max_unit, block_config.speed_min_unit
+                        ),
+                        "invalid speed units".to_string(),
+                    ),
+                ));
+            }
+        }
+
         let format_alt = if let Some(f) = block_config.format_alt {
             Some(
                 FormatTemplate::from_string(&f)
@@ -522,6 +542,7 @@ impl ConfigBlock for Net {
                 .with_spacing(Spacing::Inline),
             use_bits: block_config.use_bits,
             speed_min_unit: block_config.speed_min_unit,
+            speed_max_unit: block_config.speed_max_unit,
             speed_digits: block_config.speed_digits,
             ssid: None,
             max_ssid_width: block_config.max_ssid_width,
@@ -684,7 +705,17 @@ impl Net {
             } as f64,
             self.speed_digits,
             &self.speed_min_unit.to_string(),
+            &self
+                .speed_max_unit
+                .map(|unit| unit.to_string())
+                .unwrap_or_default(),
             if self.use_bits { "b" } else { "B" },
+            if self.use_bits {
+                Base::Decimal
+            } else {
+                Base::Binary
+            },
+            &self.shared_config,
         );
 
         self.tx_buff.remove(0);
@@ -704,7 +735,17 @@ impl Net {
             } as f64,
             self.speed_digits,
             &self.speed_min_unit.to_string(),
+            &self
+                .speed_max_unit
+                .map(|unit| unit.to_string())
+                .unwrap_or_default(),
             if self.use_bits { "b" } else { "B" },
+            if self.use_bits {
+                Base::Decimal
+            } else {
+                Base::Binary
+            },
+            &self.shared_config,
         );
 
         self.rx_buff.remove(0);
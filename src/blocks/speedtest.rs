@@ -13,7 +13,7 @@ use crate::de::deserialize_duration;
 use crate::errors::*;
 use crate::input::{I3BarEvent, MouseButton};
 use crate::scheduler::Task;
-use crate::util::format_number;
+use crate::util::{format_number, Base};
 use crate::widgets::text::TextWidget;
 use crate::widgets::{I3BarWidget, State};
 
@@ -22,6 +22,7 @@ pub struct SpeedTest {
     vals: Arc<Mutex<(bool, Vec<f32>)>>,
     text: Vec<TextWidget>,
     config: SpeedTestConfig,
+    shared_config: SharedConfig,
     send: Sender<()>,
 }
 
@@ -178,13 +179,14 @@ impl ConfigBlock for SpeedTest {
                 TextWidget::new(id, 1, shared_config.clone())
                     .with_icon("net_down")
                     .with_text(&format!("0{}", ty)),
-                TextWidget::new(id, 2, shared_config)
+                TextWidget::new(id, 2, shared_config.clone())
                     .with_icon("net_up")
                     .with_text(&format!("0{}", ty)),
             ],
             id,
             send,
             config: block_config,
+            shared_config,
         })
     }
 }
@@ -203,18 +205,40 @@ impl Block for SpeedTest {
                 let ping = vals[0] as f64 / 1_000.0;
                 let down = vals[1] as f64 * 1_000_000.0;
                 let up = vals[2] as f64 * 1_000_000.0;
-                self.text[0].set_text(format_number(ping, self.config.speed_digits, "", "s"));
+                self.text[0].set_text(format_number(
+                    ping,
+                    self.config.speed_digits,
+                    "",
+                    "",
+                    "s",
+                    Base::Decimal,
+                    &self.shared_config,
+                ));
                 self.text[1].set_text(format_number(
                     down,
                     self.config.speed_digits,
                     &self.config.speed_min_unit.to_string(),
+                    "",
                     if self.config.bytes { "B/s" } else { "b/s" },
+                    if self.config.bytes {
+                        Base::Binary
+                    } else {
+                        Base::Decimal
+                    },
+                    &self.shared_config,
                 ));
                 self.text[2].set_text(format_number(
                     up,
                     self.config.speed_digits,
                     &self.config.speed_min_unit.to_string(),
+                    "",
                     if self.config.bytes { "B/s" } else { "b/s" },
+                    if self.config.bytes {
+                        Base::Binary
+                    } else {
+                        Base::Decimal
+                    },
+                    &self.shared_config,
                 ));
 
                 // ping is in seconds
@@ -27,6 +27,23 @@ impl Default for TemperatureScale {
     }
 }
 
+impl TemperatureScale {
+    /// Converts a temperature in °C to this scale
+    pub fn convert(self, celsius: f64) -> f64 {
+        match self {
+            Self::Celsius => celsius,
+            Self::Fahrenheit => celsius * 9. / 5. + 32.,
+        }
+    }
+
+    pub fn unit(self) -> &'static str {
+        match self {
+            Self::Celsius => "°C",
+            Self::Fahrenheit => "°F",
+        }
+    }
+}
+
 pub struct Temperature {
     id: usize,
     text: TextWidget,
@@ -312,3 +329,16 @@ impl Block for Temperature {
         self.id
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::blocks::temperature::TemperatureScale;
+
+    #[test]
+    fn test_scale() {
+        assert_eq!(TemperatureScale::Celsius.convert(30.), 30.);
+        assert_eq!(TemperatureScale::Fahrenheit.convert(30.), 86.);
+        assert_eq!(TemperatureScale::Fahrenheit.convert(-40.), -40.);
+        assert_eq!(TemperatureScale::Fahrenheit.unit(), "°F");
+    }
+}
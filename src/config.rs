@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::rc::Rc;
 
 use serde::de::{Deserialize, Deserializer};
@@ -9,6 +10,7 @@ use crate::errors;
 use crate::icons::Icons;
 use crate::input::MouseButton;
 use crate::themes::Theme;
+use crate::widgets::State;
 
 #[derive(Debug)]
 pub struct SharedConfig {
@@ -16,6 +18,10 @@ pub struct SharedConfig {
     icons: Rc<Icons>,
     icons_format: String,
     pub scrolling: Scrolling,
+    pub iec_suffix: bool,
+    pub decimal_separator: char,
+    /// Icons replacing a widget's own icon while it is in a given state
+    state_icons: Rc<HashMap<State, String>>,
 }
 
 impl SharedConfig {
@@ -25,6 +31,9 @@ impl SharedConfig {
             icons: Rc::new(config.icons.clone()),
             icons_format: config.icons_format.clone(),
             scrolling: config.scrolling,
+            iec_suffix: config.iec_suffix,
+            decimal_separator: config.decimal_separator,
+            state_icons: Rc::new(HashMap::new()),
         }
     }
 
@@ -58,6 +67,42 @@ impl SharedConfig {
         Ok(())
     }
 
+    pub fn state_icons_override(
+        &mut self,
+        overrides: &HashMap<String, String>,
+    ) -> errors::Result<()> {
+        let mut state_icons = self.state_icons.as_ref().clone();
+        for entry in overrides {
+            let state = match entry.0.as_str() {
+                "idle" => State::Idle,
+                "info" => State::Info,
+                "good" => State::Good,
+                "warning" => State::Warning,
+                "critical" => State::Critical,
+                x => {
+                    return Err(errors::ConfigurationError(
+                        format!("\"{}\" is not a state that can have an icon", x),
+                        (String::new(), String::new()),
+                    ))
+                }
+            };
+            if !self.icons.0.contains_key(entry.1) {
+                return Err(errors::ConfigurationError(
+                    format!("\"{}\" is not a known icon", entry.1),
+                    (String::new(), String::new()),
+                ));
+            }
+            state_icons.insert(state, entry.1.to_string());
+        }
+        self.state_icons = Rc::new(state_icons);
+        Ok(())
+    }
+
+    /// Returns the icon configured for `state`, if any
+    pub fn get_state_icon(&self, state: State) -> Option<String> {
+        self.get_icon(self.state_icons.get(&state)?)
+    }
+
     pub fn get_icon(&self, icon: &str) -> Option<String> {
         Some(
             self.icons_format
@@ -74,6 +119,9 @@ impl Default for SharedConfig {
             icons: Rc::new(Icons::default()),
             icons_format: " {icon} ".to_string(),
             scrolling: Scrolling::default(),
+            iec_suffix: true,
+            decimal_separator: '.',
+            state_icons: Rc::new(HashMap::new()),
         }
     }
 }
@@ -85,6 +133,9 @@ impl Clone for SharedConfig {
             icons: Rc::clone(&self.icons),
             icons_format: self.icons_format.clone(),
             scrolling: self.scrolling,
+            iec_suffix: self.iec_suffix,
+            decimal_separator: self.decimal_separator,
+            state_icons: Rc::clone(&self.state_icons),
         }
     }
 }
@@ -108,6 +159,21 @@ pub struct Config {
     #[serde(default)]
     pub scrolling: Scrolling,
 
+    /// Whether binary (1024-based) numbers use `Ki`, `Mi`, `Gi`, ... suffixes rather than `K`,
+    /// `M`, `G`, ...
+    #[serde(default = "Config::default_iec_suffix")]
+    pub iec_suffix: bool,
+
+    /// Character separating the integer and fractional parts of numbers passed through
+    /// `format_number`, i.e. the speeds of the net and speedtest blocks
+    #[serde(default = "Config::default_decimal_separator")]
+    pub decimal_separator: char,
+
+    /// File to which block errors built with `errors::block_error` are appended as a JSON line.
+    /// Disabled if unset.
+    #[serde(default)]
+    pub error_log: Option<PathBuf>,
+
     #[serde(rename = "block", deserialize_with = "deserialize_blocks")]
     pub blocks: Vec<(String, value::Value)>,
 }
@@ -116,6 +182,14 @@ impl Config {
     fn default_icons_format() -> String {
         " {icon} ".to_string()
     }
+
+    fn default_iec_suffix() -> bool {
+        true
+    }
+
+    fn default_decimal_separator() -> char {
+        '.'
+    }
 }
 
 impl Default for Config {
@@ -125,6 +199,9 @@ impl Default for Config {
             theme: Theme::default(),
             icons_format: Config::default_icons_format(),
             scrolling: Scrolling::default(),
+            iec_suffix: Config::default_iec_suffix(),
+            decimal_separator: Config::default_decimal_separator(),
+            error_log: None,
             blocks: Vec::new(),
         }
     }
@@ -181,7 +258,7 @@ where
 
 #[cfg(test)]
 mod tests {
-    use crate::config::Config;
+    use crate::config::{Config, SharedConfig};
     use crate::util::deserialize_file;
     use assert_fs::prelude::{FileWriteStr, PathChild};
     use assert_fs::TempDir;
@@ -228,4 +305,18 @@ mod tests {
         let config: Result<Config, _> = deserialize_file(config_file_path.path());
         config.unwrap();
     }
+
+    #[test]
+    fn test_theme_override() {
+        let mut shared_config = SharedConfig::default();
+        let base = shared_config.clone();
+        let overrides = map_to_owned!("critical_bg" => "#ff0000");
+        shared_config.theme_override(&overrides).unwrap();
+        assert_eq!(shared_config.theme.critical_bg, Some("#ff0000".to_string()));
+        // Other blocks sharing the base theme are not affected
+        assert_ne!(base.theme.critical_bg, Some("#ff0000".to_string()));
+
+        let overrides = map_to_owned!("critical" => "#ff0000");
+        assert!(shared_config.theme_override(&overrides).is_err());
+    }
 }
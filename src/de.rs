@@ -39,7 +39,9 @@ where
             if value == "once" {
                 Ok(Update::Once)
             } else {
-                Err(de::Error::custom(r#"expected "once""#))
+                parse_duration(value)
+                    .map(Into::into)
+                    .ok_or_else(|| de::Error::custom(r#"expected "once" or a duration"#))
             }
         }
     }
@@ -57,7 +59,7 @@ where
         type Value = Duration;
 
         fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-            formatter.write_str("i64, f64 or map")
+            formatter.write_str(r#"i64, f64, map or a string like "500ms", "2s", "1m" or "1h""#)
         }
 
         fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
@@ -74,6 +76,14 @@ where
             Ok(Duration::new(0, (value * 1_000_000_000f64) as u32))
         }
 
+        fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            parse_duration(value)
+                .ok_or_else(|| de::Error::invalid_value(de::Unexpected::Str(value), &self))
+        }
+
         fn visit_map<A>(self, visitor: A) -> Result<Self::Value, A::Error>
         where
             A: de::MapAccess<'de>,
@@ -85,6 +95,27 @@ where
     deserializer.deserialize_any(DurationWrapper)
 }
 
+/// Parses a duration such as `"500ms"`, `"2s"`, `"1.5m"` or `"1h"`. A bare number is
+/// interpreted as seconds.
+fn parse_duration(s: &str) -> Option<Duration> {
+    let s = s.trim();
+    let (number, multiplier) = if let Some(number) = s.strip_suffix("ms") {
+        (number, 0.001)
+    } else if let Some(number) = s.strip_suffix('s') {
+        (number, 1.)
+    } else if let Some(number) = s.strip_suffix('m') {
+        (number, 60.)
+    } else if let Some(number) = s.strip_suffix('h') {
+        (number, 3600.)
+    } else {
+        (s, 1.)
+    };
+
+    let seconds = number.trim().parse::<f64>().ok()? * multiplier;
+    // Rejects negative, infinite and too large values instead of panicking
+    Duration::try_from_secs_f64(seconds).ok()
+}
+
 pub fn deserialize_opt_duration<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
 where
     D: Deserializer<'de>,
@@ -126,6 +157,23 @@ mod tests {
         assert_eq!(Duration::new(0, 500_000_000), deserialized.interval);
     }
 
+    #[test]
+    fn test_deserialize_duration_str() {
+        let parse = |value: &str| -> Result<Duration, toml::de::Error> {
+            let duration_toml = format!(r#""interval"= "{}""#, value);
+            toml::from_str::<DurationConfig>(&duration_toml).map(|c| c.interval)
+        };
+        assert_eq!(parse("500ms").unwrap(), Duration::from_millis(500));
+        assert_eq!(parse("2s").unwrap(), Duration::from_secs(2));
+        assert_eq!(parse("1.5m").unwrap(), Duration::from_secs(90));
+        assert_eq!(parse("1h").unwrap(), Duration::from_secs(3600));
+        assert_eq!(parse("5").unwrap(), Duration::from_secs(5));
+        assert!(parse("5 parsecs").is_err());
+        assert!(parse("-1s").is_err());
+        assert!(parse("1e20h").is_err());
+        assert!(parse("inf").is_err());
+    }
+
     #[derive(Deserialize, Debug, Clone)]
     #[serde(deny_unknown_fields)]
     pub struct UpdateConfig {
@@ -145,5 +193,8 @@ mod tests {
         let duration_toml = r#""interval"= "once""#;
         let deserialized: UpdateConfig = toml::from_str(duration_toml).unwrap();
         assert_eq!(Once, deserialized.interval);
+        let duration_toml = r#""interval"= "250ms""#;
+        let deserialized: UpdateConfig = toml::from_str(duration_toml).unwrap();
+        assert_eq!(Every(Duration::from_millis(250)), deserialized.interval);
     }
 }
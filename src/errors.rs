@@ -1,11 +1,61 @@
 pub use std::error::Error as StdError;
 use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use chrono::Local;
+use lazy_static::lazy_static;
+use serde_json::json;
 
 pub use self::Error::{BlockError, ConfigurationError, InternalError};
 
 /// Result type returned from functions that can have our `Error`s.
 pub type Result<T> = ::std::result::Result<T, Error>;
 
+lazy_static! {
+    static ref ERROR_LOG: Mutex<Option<File>> = Mutex::new(None);
+}
+
+/// Checked before touching `ERROR_LOG`, so block errors cost nothing extra when no log is set.
+static ERROR_LOG_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Appends every subsequent error raised through `block_error()` to `path` as a JSON line.
+pub fn set_error_log(path: &Path) -> Result<()> {
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .configuration_error(&format!("failed to open error log {}", path.display()))?;
+    *ERROR_LOG.lock().unwrap() = Some(file);
+    ERROR_LOG_ENABLED.store(true, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Builds a `BlockError`, appending it to the error log if one is set.
+pub fn block_error(block: &str, message: &str) -> Error {
+    if ERROR_LOG_ENABLED.load(Ordering::Relaxed) {
+        log_block_error(block, message);
+    }
+    BlockError(block.to_owned(), message.to_owned())
+}
+
+fn log_block_error(block: &str, message: &str) {
+    let line = json!({
+        "block": block,
+        "message": message,
+        "timestamp": Local::now().to_rfc3339(),
+    });
+    if let Ok(mut log) = ERROR_LOG.lock() {
+        if let Some(file) = log.as_mut() {
+            // Failing to log must never take the bar down
+            let _ = writeln!(file, "{}", line);
+        }
+    }
+}
+
 pub trait ResultExtBlock<T, E> {
     fn block_error(self, block: &str, message: &str) -> Result<T>;
 }
@@ -17,7 +67,7 @@ pub trait ResultExtInternal<T, E> {
 
 impl<T, E> ResultExtBlock<T, E> for ::std::result::Result<T, E> {
     fn block_error(self, block: &str, message: &str) -> Result<T> {
-        self.map_err(|_| BlockError(block.to_owned(), message.to_owned()))
+        self.map_err(|_| block_error(block, message))
     }
 }
 
@@ -49,7 +99,7 @@ pub trait OptionExt<T> {
 
 impl<T> OptionExt<T> for ::std::option::Option<T> {
     fn block_error(self, block: &str, message: &str) -> Result<T> {
-        self.ok_or_else(|| BlockError(block.to_owned(), message.to_owned()))
+        self.ok_or_else(|| block_error(block, message))
     }
 
     fn internal_error(self, context: &str, message: &str) -> Result<T> {
@@ -125,3 +175,37 @@ where
         InternalError("unknown".to_string(), "send error".to_string(), None)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::*;
+    use assert_fs::prelude::PathChild;
+    use assert_fs::TempDir;
+    use std::fs;
+
+    #[test]
+    fn test_error_log() {
+        let dir = TempDir::new().unwrap();
+        let log = dir.child("errors.log");
+        set_error_log(log.path()).unwrap();
+
+        assert!(Err::<(), ()>(()).block_error("test", "first").is_err());
+        assert!(None::<()>.block_error("test", "second").is_err());
+
+        // Other tests may log concurrently, so only look at our own lines
+        let lines: Vec<serde_json::Value> = fs::read_to_string(log.path())
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .filter(|line: &serde_json::Value| line["block"] == "test")
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["message"], "first");
+        assert_eq!(lines[1]["message"], "second");
+        assert!(lines[0]["timestamp"].is_string());
+
+        // Don't leave the log enabled for the rest of the test run
+        ERROR_LOG_ENABLED.store(false, Ordering::Relaxed);
+        *ERROR_LOG.lock().unwrap() = None;
+    }
+}
@@ -43,6 +43,7 @@ lazy_static! {
         "cpu" => "CPU",
         "disk_drive" => "DISK",
         "docker" => "DOCKER",
+        "fan" => "FAN",
         "github" => "GITHUB",
         "gpu" => "GPU",
         "headphones" => "HEAD",
@@ -127,6 +128,7 @@ lazy_static! {
         "cpu" => "\u{f0e4}", // fa-dashboard
         "disk_drive" => "\u{f0a0}", // fa-hdd-o
         "docker" => "\u{f21a}", // fa-ship
+        "fan" => "\u{f085}", // fa-cogs
         "github" => "\u{f09b}", // fa-github
         "gpu" => "\u{f26c}", // fa-television
         "headphones" => "\u{f025}", // fa-headphones
@@ -213,6 +215,7 @@ lazy_static! {
         "cpu" => "\u{f3fd}",
         "disk_drive" => "\u{f8b5}",
         "docker" => "\u{f21a}",
+        "fan" => "\u{f863}",
         "github" => "\u{f09b}",
         "gpu" => "\u{f26c}",
         "headphones" => "\u{f025}",
@@ -286,6 +289,7 @@ lazy_static! {
         "cpu" => "\u{e640}", // network_check
         "disk_drive" => "\u{e1db}", // storage
         "docker" => "\u{e532}", // directions_boat
+        "fan" => "\u{e332}", // toys
         "github" => "\u{e86f}", // code
         "gpu" => "\u{e333}", // tv
         "headphones" => "\u{e60f}", // bluetooth_audio
@@ -362,6 +366,7 @@ lazy_static! {
         "cpu" => "\u{f9c4}", // nf-mdi-speedometer
         "disk_drive" => "\u{f7c9}", // nf-mdi-harddisk
         "docker" => "\u{f308}", // nf-linux-docker
+        "fan" => "\u{f70f}", // nf-mdi-fan
         "github" => "\u{f7a3}", // nf-mdi-github_circle
         "gpu" => "\u{f878}", // nf-mdi-monitor
         "headphones" => "\u{f7ca}", // nf-mdi-headphones
@@ -8,14 +8,26 @@ use crossbeam_channel::Sender;
 use serde::{de, Deserializer};
 use serde_derive::Deserialize;
 
+/// Mouse button of a click event, decoded from the X11 button number sent by i3bar.
+///
+/// Scrolling is reported as a click of `WheelUp` (button 4) or `WheelDown` (button 5). Blocks
+/// reacting to scrolling should use `Scrolling::to_logical_direction` rather than matching these
+/// variants directly, so the user's `scrolling` setting is respected.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum MouseButton {
+    /// Button 1
     Left,
+    /// Button 2
     Middle,
+    /// Button 3
     Right,
+    /// Button 4
     WheelUp,
+    /// Button 5
     WheelDown,
+    /// Button 9
     Forward, // On my mouse, these map to forward and back
+    /// Button 8
     Back,
     Unknown,
 }
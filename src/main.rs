@@ -141,6 +141,10 @@ fn run(matches: &ArgMatches) -> Result<()> {
     };
     let config: Config = deserialize_file(&config_path)?;
 
+    if let Some(ref error_log) = config.error_log {
+        set_error_log(error_log)?;
+    }
+
     // Update request channel
     let (tx_update_requests, rx_update_requests): (Sender<Task>, Receiver<Task>) =
         crossbeam_channel::unbounded();
@@ -188,6 +192,8 @@ fn run(matches: &ArgMatches) -> Result<()> {
     let mut ttnu = crossbeam_channel::after(Duration::from_millis(0));
 
     let one_shot = matches.is_present("one-shot");
+    // The last status line sent to the bar, to avoid repeating identical ones
+    let mut last_line = String::new();
     loop {
         // We use the message passing concept of channel selection
         // to avoid busy wait
@@ -198,7 +204,7 @@ fn run(matches: &ArgMatches) -> Result<()> {
                         blocks.get_mut(id)
                     .internal_error("click handler", "could not get required block")?
                             .click(&event)?;
-                    util::print_blocks(&blocks, &shared_config)?;
+                    util::print_blocks(&blocks, &shared_config, &mut last_line)?;
                 }
             },
             // Receive async update requests
@@ -207,13 +213,13 @@ fn run(matches: &ArgMatches) -> Result<()> {
                 blocks.get_mut(req.id)
                     .internal_error("scheduler", "could not get required block")?
                     .update()?;
-                util::print_blocks(&blocks, &shared_config)?;
+                util::print_blocks(&blocks, &shared_config, &mut last_line)?;
             },
             // Receive update timer events
             recv(ttnu) -> _ => {
                 scheduler.do_scheduled_updates(&mut blocks)?;
                 // redraw the blocks, state changed
-                util::print_blocks(&blocks, &shared_config)?;
+                util::print_blocks(&blocks, &shared_config, &mut last_line)?;
             },
             // Receive signal events
             recv(rx_signals) -> res => if let Ok(sig) = res {
@@ -223,7 +229,14 @@ fn run(matches: &ArgMatches) -> Result<()> {
                         for block in blocks.iter_mut() {
                             block.update()?;
                         }
-                        util::print_blocks(&blocks, &shared_config)?;
+                        util::print_blocks(&blocks, &shared_config, &mut last_line)?;
+                    },
+                    signal_hook::consts::SIGTERM | signal_hook::consts::SIGINT => {
+                        // Let blocks stop their children and threads before exiting
+                        for block in blocks.iter_mut() {
+                            block.shutdown();
+                        }
+                        return Ok(());
                     },
                     signal_hook::consts::SIGUSR2 => {
                         //USR2 signal that should reload the config
@@ -122,3 +122,46 @@ impl UpdateScheduler {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::blocks::{Block, Update};
+    use crate::errors::*;
+    use crate::scheduler::UpdateScheduler;
+    use crate::widgets::I3BarWidget;
+
+    /// A block returning a different update interval on each call
+    struct IntervalBlock {
+        intervals: Vec<Duration>,
+    }
+
+    impl Block for IntervalBlock {
+        fn id(&self) -> usize {
+            0
+        }
+
+        fn view(&self) -> Vec<&dyn I3BarWidget> {
+            Vec::new()
+        }
+
+        fn update(&mut self) -> Result<Option<Update>> {
+            Ok(Some(self.intervals.remove(0).into()))
+        }
+    }
+
+    #[test]
+    fn test_reschedule_with_latest_interval() {
+        let mut blocks: Vec<Box<dyn Block>> = vec![Box::new(IntervalBlock {
+            intervals: vec![Duration::from_millis(10), Duration::from_secs(3600)],
+        })];
+        let mut scheduler = UpdateScheduler::new(&blocks);
+
+        scheduler.do_scheduled_updates(&mut blocks).unwrap();
+        assert!(scheduler.time_to_next_update().unwrap() <= Duration::from_millis(10));
+
+        scheduler.do_scheduled_updates(&mut blocks).unwrap();
+        assert!(scheduler.time_to_next_update().unwrap() > Duration::from_secs(3500));
+    }
+}
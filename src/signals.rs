@@ -17,9 +17,17 @@ pub fn process_signals(sender: Sender<i32>) {
                 let mut signals = (sigmin..sigmax).collect::<Vec<_>>();
                 signals.push(signal_hook::consts::SIGUSR1);
                 signals.push(signal_hook::consts::SIGUSR2);
+                signals.push(signal_hook::consts::SIGTERM);
+                signals.push(signal_hook::consts::SIGINT);
                 let mut signals = signal_hook::iterator::Signals::new(&signals).unwrap();
                 for sig in signals.forever() {
-                    sender.send(sig).unwrap();
+                    let stop =
+                        sig == signal_hook::consts::SIGTERM || sig == signal_hook::consts::SIGINT;
+                    if sender.send(sig).is_err() && stop {
+                        // The main loop is gone (e.g. an error is being shown), so nobody else
+                        // will exit for us
+                        let _ = signal_hook::low_level::emulate_default_handler(sig);
+                    }
                 }
             }
         })
@@ -70,24 +70,94 @@ pub fn escape_pango_text(text: String) -> String {
         .collect()
 }
 
+/// The base used when scaling a number to a suffix
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Base {
+    /// Scale by powers of 1024 (used for bytes)
+    Binary,
+    /// Scale by powers of 1000 and emit `K`, `M`, `G`, ... suffixes
+    Decimal,
+}
+
+impl Base {
+    fn scale(self) -> f64 {
+        match self {
+            Base::Binary => 1024.,
+            Base::Decimal => 1000.,
+        }
+    }
+
+    /// Returns the exponent level of `value`, i.e. the number of times it can be divided by the
+    /// base before dropping below 1
+    fn exp_level(self, value: f64) -> i32 {
+        match self {
+            // log2 is exact for powers of two, so avoid `log(1024)` rounding errors
+            Base::Binary => value.log2().div_euclid(10.) as i32,
+            Base::Decimal => value.log10().div_euclid(3.) as i32,
+        }
+    }
+}
+
+fn suffix_exp_level(suffix: &str) -> Option<i32> {
+    match suffix {
+        "P" => Some(5),
+        "T" => Some(4),
+        "G" => Some(3),
+        "M" => Some(2),
+        "K" => Some(1),
+        "1" | "B" => Some(0),
+        "m" => Some(-1),
+        "u" => Some(-2),
+        "n" => Some(-3),
+        _ => None,
+    }
+}
+
 /// Format `raw_value` to engineering notation
-pub fn format_number(raw_value: f64, total_digits: usize, min_suffix: &str, unit: &str) -> String {
-    let min_exp_level = match min_suffix {
-        "T" => 4,
-        "G" => 3,
-        "M" => 2,
-        "K" => 1,
-        "1" => 0,
-        "m" => -1,
-        "u" => -2,
-        "n" => -3,
-        _ => -4,
+///
+/// The chosen suffix is bounded by `min_suffix` and `max_suffix`; an empty or unknown suffix
+/// leaves that side unbounded. Numbers below 1 therefore get `m`, `u`, ... suffixes unless
+/// `min_suffix` is `"1"`, in which case they are printed with `total_digits - 1` decimals and no
+/// suffix. The suffix style and decimal separator are taken from `config`.
+/// NaN is rendered as `—` and infinities as `∞`/`-∞`.
+pub fn format_number(
+    raw_value: f64,
+    total_digits: usize,
+    min_suffix: &str,
+    max_suffix: &str,
+    unit: &str,
+    base: Base,
+    config: &SharedConfig,
+) -> String {
+    if raw_value.is_nan() {
+        return "—".to_string();
+    }
+    if raw_value.is_infinite() {
+        return if raw_value > 0. { "∞" } else { "-∞" }.to_string();
+    }
+
+    let min_exp_level = suffix_exp_level(min_suffix).unwrap_or(-4);
+    // Fractions of a byte make no sense, so never go below `1` for binary values
+    let min_exp_level = match base {
+        Base::Binary => min_exp_level.max(0),
+        Base::Decimal => min_exp_level,
     };
 
-    let exp_level = (raw_value.log10().div_euclid(3.) as i32).clamp(min_exp_level, 4);
-    let value = raw_value / (10f64).powi(exp_level * 3);
+    let max_exp_level = suffix_exp_level(max_suffix).unwrap_or(5).max(min_exp_level);
+
+    // The exponent is derived from the magnitude, so negative values keep their sign and zero
+    // does not end up with the smallest possible suffix
+    let magnitude = raw_value.abs();
+    let exp_level = if magnitude == 0. {
+        0
+    } else {
+        base.exp_level(magnitude)
+    }
+    .clamp(min_exp_level, max_exp_level);
+    let value = raw_value / base.scale().powi(exp_level);
 
     let suffix = match exp_level {
+        5 => "P",
         4 => "T",
         3 => "G",
         2 => "M",
@@ -98,18 +168,30 @@ pub fn format_number(raw_value: f64, total_digits: usize, min_suffix: &str, unit
         -3 => "n",
         _ => "p",
     };
+    let binary_marker = if config.iec_suffix && base == Base::Binary && exp_level > 0 {
+        "i"
+    } else {
+        ""
+    };
 
     let total_digits = total_digits as isize;
-    let decimals = (if value >= 100. {
+    let decimals = (if base == Base::Binary && exp_level == 0 {
+        0
+    } else if value.abs() >= 1000. {
+        total_digits - 4
+    } else if value.abs() >= 100. {
         total_digits - 3
-    } else if value >= 10. {
+    } else if value.abs() >= 10. {
         total_digits - 2
     } else {
         total_digits - 1
     })
     .max(0);
 
-    format!("{:.*}{}{}", decimals as usize, value, suffix, unit)
+    let number = format!("{:.*}", decimals as usize, value)
+        .replace('.', &config.decimal_separator.to_string());
+
+    format!("{}{}{}{}", number, suffix, binary_marker, unit)
 }
 
 pub fn battery_level_to_icon(charge_level: Result<u64>) -> &'static str {
@@ -199,7 +281,13 @@ macro_rules! map_to_owned (
      };
 );
 
-pub fn print_blocks(blocks: &[Box<dyn Block>], config: &SharedConfig) -> Result<()> {
+/// Prints the status line for `blocks`, unless it is the same as `last_line` (the previously
+/// printed one), in which case nothing needs to be sent to the bar.
+pub fn print_blocks(
+    blocks: &[Box<dyn Block>],
+    config: &SharedConfig,
+    last_line: &mut String,
+) -> Result<()> {
     let mut last_bg: Option<String> = None;
 
     let mut rendered_blocks = vec![];
@@ -245,6 +333,17 @@ pub fn print_blocks(blocks: &[Box<dyn Block>], config: &SharedConfig) -> Result<
 
         alternator = !alternator;
 
+        // Wrap the block as a whole, so multi-widget blocks get a single prefix and suffix. Empty
+        // widgets are skipped, so the affixes stay next to the visible text.
+        let (prefix, suffix) = block.affixes();
+        let is_visible = |widget: &I3BlockData| !widget.full_text.trim().is_empty();
+        if let Some(first) = rendered_widgets.iter_mut().find(|w| is_visible(w)) {
+            first.full_text.insert_str(0, prefix);
+        }
+        if let Some(last) = rendered_widgets.iter_mut().rev().find(|w| is_visible(w)) {
+            last.full_text.push_str(suffix);
+        }
+
         if config.theme.native_separators == Some(true) {
             // Re-add native separator on last widget for native theme
             rendered_widgets.last_mut().unwrap().separator = None;
@@ -303,7 +402,11 @@ pub fn print_blocks(blocks: &[Box<dyn Block>], config: &SharedConfig) -> Result<
         );
     }
 
-    println!("[{}],", rendered_blocks.join(","));
+    let line = format!("[{}],", rendered_blocks.join(","));
+    if line != *last_line {
+        println!("{}", line);
+        *last_line = line;
+    }
 
     Ok(())
 }
@@ -420,64 +523,562 @@ pub struct FormatTemplate {
 enum FormatToken {
     Text(String),
     Var(String),
+    /// `{key OP number ? branch : branch}`, where a branch is either a quoted literal or a key
+    Cond {
+        key: String,
+        op: CompareOp,
+        threshold: f64,
+        then: Box<FormatToken>,
+        otherwise: Box<FormatToken>,
+    },
+}
+
+#[derive(Debug, Clone, Copy)]
+enum CompareOp {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+}
+
+impl CompareOp {
+    fn from_str(op: &str) -> Option<Self> {
+        match op {
+            "<" => Some(CompareOp::Lt),
+            "<=" => Some(CompareOp::Le),
+            ">" => Some(CompareOp::Gt),
+            ">=" => Some(CompareOp::Ge),
+            "==" => Some(CompareOp::Eq),
+            "!=" => Some(CompareOp::Ne),
+            _ => None,
+        }
+    }
+
+    fn compare(self, lhs: f64, rhs: f64) -> bool {
+        match self {
+            CompareOp::Lt => lhs < rhs,
+            CompareOp::Le => lhs <= rhs,
+            CompareOp::Gt => lhs > rhs,
+            CompareOp::Ge => lhs >= rhs,
+            CompareOp::Eq => (lhs - rhs).abs() < f64::EPSILON,
+            CompareOp::Ne => (lhs - rhs).abs() >= f64::EPSILON,
+        }
+    }
+}
+
+impl FormatToken {
+    /// Parses a branch of a conditional: a quoted literal or the name of a key
+    fn branch(branch: &str) -> Self {
+        match branch.strip_prefix('"').and_then(|b| b.strip_suffix('"')) {
+            Some(text) => FormatToken::Text(text.to_string()),
+            None => FormatToken::Var(format!("{{{}}}", branch)),
+        }
+    }
+}
+
+impl FormatToken {
+    fn collect_vars<'a>(&'a self, vars: &mut Vec<&'a str>) {
+        match self {
+            FormatToken::Text(_) => {}
+            FormatToken::Var(key) => vars.push(key),
+            FormatToken::Cond {
+                key,
+                then,
+                otherwise,
+                ..
+            } => {
+                vars.push(key);
+                then.collect_vars(vars);
+                otherwise.collect_vars(vars);
+            }
+        }
+    }
+}
+
+/// Levenshtein distance between two strings, counted in chars
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = diagonal + if ca == cb { 0 } else { 1 };
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
 }
 
 impl FormatTemplate {
     pub fn from_string(s: &str) -> Result<Self> {
         //valid var tokens: {} containing any amount of alphanumericals
-        let re = Regex::new(r"\{[a-zA-Z0-9_-]+?\}").internal_error("util", "invalid regex")?;
+        //`{{` and `}}` are escapes for literal braces
+        let re = Regex::new(concat!(
+            r"\{\{|\}\}|\{[a-zA-Z0-9_-]+?\}|",
+            r#"\{\s*(?P<key>[a-zA-Z0-9_-]+)\s*(?P<op><=|>=|==|!=|<|>)\s*(?P<threshold>-?[0-9.]+)\s*"#,
+            r#"\?\s*(?P<then>"[^"]*"|[a-zA-Z0-9_-]+)\s*:\s*(?P<otherwise>"[^"]*"|[a-zA-Z0-9_-]+)\s*\}"#,
+        ))
+        .internal_error("util", "invalid regex")?;
 
         let mut tokens = vec![];
+        let mut text = String::new();
         let mut start: usize = 0;
 
-        for re_match in re.find_iter(&s) {
-            if re_match.start() != start {
-                tokens.push(FormatToken::Text(s[start..re_match.start()].to_string()));
-            }
-            tokens.push(FormatToken::Var(re_match.as_str().to_string()));
+        for captures in re.captures_iter(s) {
+            let re_match = captures
+                .get(0)
+                .internal_error("util", "empty regex match")?;
+            text.push_str(&s[start..re_match.start()]);
             start = re_match.end();
+            let token = match re_match.as_str() {
+                "{{" => {
+                    text.push('{');
+                    continue;
+                }
+                "}}" => {
+                    text.push('}');
+                    continue;
+                }
+                var => match (
+                    captures.name("key"),
+                    captures.name("op"),
+                    captures.name("threshold"),
+                    captures.name("then"),
+                    captures.name("otherwise"),
+                ) {
+                    (Some(key), Some(op), Some(threshold), Some(then), Some(otherwise)) => {
+                        FormatToken::Cond {
+                            key: format!("{{{}}}", key.as_str()),
+                            op: CompareOp::from_str(op.as_str())
+                                .internal_error("util", "invalid comparison operator")?,
+                            threshold: threshold.as_str().parse::<f64>().configuration_error(
+                                &format!("invalid number in format string: {}", var),
+                            )?,
+                            then: Box::new(FormatToken::branch(then.as_str())),
+                            otherwise: Box::new(FormatToken::branch(otherwise.as_str())),
+                        }
+                    }
+                    _ => FormatToken::Var(var.to_string()),
+                },
+            };
+            if !text.is_empty() {
+                tokens.push(FormatToken::Text(std::mem::take(&mut text)));
+            }
+            tokens.push(token);
         }
 
-        if start != s.len() {
-            tokens.push(FormatToken::Text(s[start..].to_string()));
+        text.push_str(&s[start..]);
+        if !text.is_empty() {
+            tokens.push(FormatToken::Text(text));
         }
 
         Ok(FormatTemplate { tokens })
     }
 
+    /// Checks that every placeholder is one of `keys` (written like the keys of the values map,
+    /// e.g. `"{gpu}"`), so that typos are reported when the block is created instead of when it
+    /// is first rendered. `block` names the block in the error message.
+    pub fn with_keys(self, block: &str, keys: &[&str]) -> Result<Self> {
+        let mut used = vec![];
+        for token in &self.tokens {
+            token.collect_vars(&mut used);
+        }
+
+        if let Some(unknown) = used.into_iter().find(|var| !keys.contains(var)) {
+            let suggestion = keys
+                .iter()
+                .map(|key| (edit_distance(unknown, key), key))
+                .filter(|&(distance, _)| distance <= 2)
+                .min()
+                .map(|(_, key)| format!(" (did you mean '{}'?)", key.trim_matches(&['{', '}'][..])))
+                .unwrap_or_default();
+            return Err(ConfigurationError(
+                format!(
+                    "{}: unknown variable '{}' in format string{}",
+                    block,
+                    unknown.trim_matches(&['{', '}'][..]),
+                    suggestion
+                ),
+                (
+                    format!(
+                        "valid variables are {}",
+                        keys.iter()
+                            .map(|key| key.trim_matches(&['{', '}'][..]))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                    "unknown variable".to_string(),
+                ),
+            ));
+        }
+
+        Ok(self)
+    }
+
     pub fn render_static_str<T: Display>(&self, vars: &HashMap<&str, T>) -> Result<String> {
         let mut rendered = String::new();
 
         for token in &self.tokens {
-            match token {
-                FormatToken::Text(text) => rendered.push_str(&text),
-                FormatToken::Var(ref key) => rendered.push_str(&format!(
-                    "{}",
-                    vars.get(&**key).internal_error(
-                        "util",
-                        &format!("Unknown placeholder in format string: {}", key),
-                    )?
-                )),
-            }
+            Self::render_token(token, vars, &mut rendered)?;
         }
 
         Ok(rendered)
     }
+
+    fn render_token<T: Display>(
+        token: &FormatToken,
+        vars: &HashMap<&str, T>,
+        rendered: &mut String,
+    ) -> Result<()> {
+        let get_var = |key: &str| {
+            vars.get(key).internal_error(
+                "util",
+                &format!("Unknown placeholder in format string: {}", key),
+            )
+        };
+
+        match token {
+            FormatToken::Text(text) => rendered.push_str(text),
+            FormatToken::Var(ref key) => rendered.push_str(&format!("{}", get_var(key)?)),
+            FormatToken::Cond {
+                ref key,
+                op,
+                threshold,
+                ref then,
+                ref otherwise,
+            } => {
+                // Values that are not plain numbers never satisfy the condition
+                let holds = format!("{}", get_var(key)?)
+                    .trim()
+                    .parse::<f64>()
+                    .map(|value| op.compare(value, *threshold))
+                    .unwrap_or(false);
+                Self::render_token(if holds { then } else { otherwise }, vars, rendered)?;
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::util::{color_from_rgba, format_number, has_command};
+    use crate::config::SharedConfig;
+    use crate::util::{color_from_rgba, format_number, has_command, Base, FormatTemplate};
 
     #[test]
     fn test_format_number() {
-        assert_eq!(format_number(1.0, 3, "", "s"), "1.00s");
-        assert_eq!(format_number(1.007, 3, "", "s"), "1.01s");
-        assert_eq!(format_number(1.007, 4, "K", "s"), "0.001Ks");
-        assert_eq!(format_number(1007., 3, "K", "s"), "1.01Ks");
-        assert_eq!(format_number(107_000., 3, "", "s"), "107Ks");
-        assert_eq!(format_number(107., 3, "", "s"), "107s");
-        assert_eq!(format_number(0.000_123_123, 3, "", "N"), "123uN");
+        let config = SharedConfig::default();
+        assert_eq!(
+            format_number(1.0, 3, "", "", "s", Base::Decimal, &config),
+            "1.00s"
+        );
+        assert_eq!(
+            format_number(1.007, 3, "", "", "s", Base::Decimal, &config),
+            "1.01s"
+        );
+        assert_eq!(
+            format_number(1.007, 4, "K", "", "s", Base::Decimal, &config),
+            "0.001Ks"
+        );
+        assert_eq!(
+            format_number(1007., 3, "K", "", "s", Base::Decimal, &config),
+            "1.01Ks"
+        );
+        assert_eq!(
+            format_number(107_000., 3, "", "", "s", Base::Decimal, &config),
+            "107Ks"
+        );
+        assert_eq!(
+            format_number(107., 3, "", "", "s", Base::Decimal, &config),
+            "107s"
+        );
+        assert_eq!(
+            format_number(0.000_123_123, 3, "", "", "N", Base::Decimal, &config),
+            "123uN"
+        );
+    }
+
+    #[test]
+    fn test_format_number_binary() {
+        let config = SharedConfig::default();
+        assert_eq!(
+            format_number(1023., 3, "", "", "B", Base::Binary, &config),
+            "1023B"
+        );
+        assert_eq!(
+            format_number(1024., 3, "", "", "B", Base::Binary, &config),
+            "1.00KiB"
+        );
+        assert_eq!(
+            format_number(1536., 3, "", "", "B", Base::Binary, &config),
+            "1.50KiB"
+        );
+        assert_eq!(
+            format_number(1_048_576., 3, "", "", "B", Base::Binary, &config),
+            "1.00MiB"
+        );
+        assert_eq!(
+            format_number(1024., 3, "", "", "B", Base::Decimal, &config),
+            "1.02KB"
+        );
+    }
+
+    #[test]
+    fn test_format_number_suffix_style() {
+        let config = SharedConfig::default();
+        assert_eq!(
+            format_number(1_048_576., 3, "", "", "B", Base::Binary, &config),
+            "1.00MiB"
+        );
+        let mut config = config;
+        config.iec_suffix = false;
+        assert_eq!(
+            format_number(1_048_576., 3, "", "", "B", Base::Binary, &config),
+            "1.00MB"
+        );
+        // Decimal suffixes never get the marker
+        assert_eq!(
+            format_number(1_000_000., 3, "", "", "B", Base::Decimal, &config),
+            "1.00MB"
+        );
+    }
+
+    #[test]
+    fn test_format_number_decimal_separator() {
+        let mut config = SharedConfig::default();
+        config.decimal_separator = ',';
+        assert_eq!(
+            format_number(1.5e9, 3, "", "", "B", Base::Decimal, &config),
+            "1,50GB"
+        );
+        assert_eq!(
+            format_number(1536., 3, "", "", "B", Base::Binary, &config),
+            "1,50KiB"
+        );
+        // Values without fractional digits are unaffected
+        assert_eq!(
+            format_number(107., 3, "", "", "s", Base::Decimal, &config),
+            "107s"
+        );
+        assert_eq!(
+            format_number(1023., 3, "", "", "B", Base::Binary, &config),
+            "1023B"
+        );
+    }
+
+    #[test]
+    fn test_format_number_zero_and_negative() {
+        let config = SharedConfig::default();
+        assert_eq!(
+            format_number(0.0, 3, "", "", "s", Base::Decimal, &config),
+            "0.00s"
+        );
+        assert_eq!(
+            format_number(0.0, 3, "K", "", "B", Base::Decimal, &config),
+            "0.00KB"
+        );
+        assert_eq!(
+            format_number(0.0, 3, "", "", "B", Base::Binary, &config),
+            "0B"
+        );
+        assert_eq!(
+            format_number(-5.0, 3, "", "", "s", Base::Decimal, &config),
+            "-5.00s"
+        );
+        assert_eq!(
+            format_number(-1500.0, 3, "", "", "s", Base::Decimal, &config),
+            "-1.50Ks"
+        );
+        assert_eq!(
+            format_number(-2048.0, 3, "", "", "B", Base::Binary, &config),
+            "-2.00KiB"
+        );
+    }
+
+    #[test]
+    fn test_format_number_not_finite() {
+        let config = SharedConfig::default();
+        assert_eq!(
+            format_number(f64::NAN, 3, "", "", "B", Base::Binary, &config),
+            "—"
+        );
+        let (used, max) = (0., 0.);
+        assert_eq!(
+            format_number(used / max * 100., 3, "", "", "%", Base::Decimal, &config),
+            "—"
+        );
+        assert_eq!(
+            format_number(f64::INFINITY, 3, "", "", "B", Base::Binary, &config),
+            "∞"
+        );
+        assert_eq!(
+            format_number(f64::NEG_INFINITY, 3, "", "", "s", Base::Decimal, &config),
+            "-∞"
+        );
+    }
+
+    #[test]
+    fn test_format_number_peta() {
+        let config = SharedConfig::default();
+        assert_eq!(
+            format_number(1.5e15, 3, "", "", "B", Base::Decimal, &config),
+            "1.50PB"
+        );
+        assert_eq!(
+            format_number(9.9e18, 3, "", "", "B", Base::Decimal, &config),
+            "9900PB"
+        );
+        assert_eq!(
+            format_number(
+                1_125_899_906_842_624.,
+                3,
+                "",
+                "",
+                "B",
+                Base::Binary,
+                &config
+            ),
+            "1.00PiB"
+        );
+        assert_eq!(
+            format_number(1.5e15, 3, "P", "", "B", Base::Decimal, &config),
+            "1.50PB"
+        );
+    }
+
+    #[test]
+    fn test_format_number_small() {
+        let config = SharedConfig::default();
+        assert_eq!(
+            format_number(0.5, 3, "", "", "", Base::Decimal, &config),
+            "500m"
+        );
+        assert_eq!(
+            format_number(0.05, 3, "", "", "", Base::Decimal, &config),
+            "50.0m"
+        );
+        assert_eq!(
+            format_number(0.005, 3, "", "", "", Base::Decimal, &config),
+            "5.00m"
+        );
+        // Without sub-unit suffixes, the width stays the same and precision is lost instead
+        assert_eq!(
+            format_number(0.5, 3, "1", "", "", Base::Decimal, &config),
+            "0.50"
+        );
+        assert_eq!(
+            format_number(0.05, 3, "1", "", "", Base::Decimal, &config),
+            "0.05"
+        );
+        assert_eq!(
+            format_number(0.005, 3, "1", "", "", Base::Decimal, &config),
+            "0.01"
+        );
+    }
+
+    #[test]
+    fn test_format_number_binary_no_fractions() {
+        let config = SharedConfig::default();
+        assert_eq!(
+            format_number(0.5, 3, "", "", "B", Base::Binary, &config),
+            "0B"
+        );
+        assert_eq!(
+            format_number(0.5, 3, "m", "", "B", Base::Binary, &config),
+            "0B"
+        );
+        assert_eq!(
+            format_number(12., 3, "", "", "B", Base::Binary, &config),
+            "12B"
+        );
+    }
+
+    #[test]
+    fn test_format_number_max_suffix() {
+        let config = SharedConfig::default();
+        assert_eq!(
+            format_number(1_500_000_000., 4, "", "M", "B", Base::Decimal, &config),
+            "1500MB"
+        );
+        assert_eq!(
+            format_number(1_500_000., 3, "", "M", "B", Base::Decimal, &config),
+            "1.50MB"
+        );
+        assert_eq!(
+            format_number(1_500., 3, "M", "K", "B", Base::Decimal, &config),
+            "0.00MB"
+        );
+    }
+
+    #[test]
+    fn test_format_template_escaped_braces() {
+        let vars = map!("{gpu}" => 42);
+        let render = |s: &str| {
+            FormatTemplate::from_string(s)
+                .unwrap()
+                .render_static_str(&vars)
+                .unwrap()
+        };
+        assert_eq!(render("{gpu}%"), "42%");
+        assert_eq!(render("{{gpu}}"), "{gpu}");
+        assert_eq!(render("{{{gpu}}}"), "{42}");
+        assert_eq!(render("{{\"gpu\": {gpu}}}"), "{\"gpu\": 42}");
+        assert_eq!(render("a { b } c"), "a { b } c");
+    }
+
+    #[test]
+    fn test_format_template_conditional() {
+        let vars = map!("{gpu}" => "95", "{vram}" => "40", "{name}" => "card0");
+        let render = |s: &str| {
+            FormatTemplate::from_string(s)
+                .unwrap()
+                .render_static_str(&vars)
+                .unwrap()
+        };
+        assert_eq!(render(r#"{gpu > 90 ? "HOT" : gpu}"#), "HOT");
+        assert_eq!(render(r#"{vram > 90 ? "HOT" : vram}%"#), "40%");
+        assert_eq!(render("{vram<=40?name:gpu}"), "card0");
+        assert_eq!(render(r#"{gpu == 95 ? "" : gpu}"#), "");
+        // Non-numeric values never satisfy the condition
+        assert_eq!(render(r#"{name > 0 ? "yes" : "no"}"#), "no");
+        assert!(FormatTemplate::from_string(r#"{foo > 1 ? "a" : "b"}"#)
+            .unwrap()
+            .render_static_str(&vars)
+            .is_err());
+    }
+
+    #[test]
+    fn test_format_template_with_keys() {
+        let keys = &["{gpu}", "{vram}", "{name}"];
+        assert!(FormatTemplate::from_string("{gpu}% {vram}%")
+            .unwrap()
+            .with_keys("json_stream", keys)
+            .is_ok());
+        assert!(FormatTemplate::from_string(r#"{gpu > 90 ? "HOT" : name}"#)
+            .unwrap()
+            .with_keys("json_stream", keys)
+            .is_ok());
+        assert_eq!(
+            FormatTemplate::from_string("{gpuu}%")
+                .unwrap()
+                .with_keys("json_stream", keys)
+                .unwrap_err()
+                .to_string(),
+            "Configuration error: json_stream: unknown variable 'gpuu' in format string (did you mean 'gpu'?)"
+        );
+        assert_eq!(
+            FormatTemplate::from_string(r#"{gpu > 90 ? "HOT" : temperature}"#)
+                .unwrap()
+                .with_keys("json_stream", keys)
+                .unwrap_err()
+                .to_string(),
+            "Configuration error: json_stream: unknown variable 'temperature' in format string"
+        );
     }
 
     #[test]
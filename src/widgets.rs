@@ -11,17 +11,22 @@ use serde_derive::Deserialize;
 use crate::themes::Theme;
 use i3block_data::I3BlockData;
 
+/// Padding added around a widget's contents.
+///
+/// Widgets never add space between blocks themselves: `separator_block_width` is always 0, so
+/// the padding below is all there is.
 #[derive(Debug, Copy, Clone, Deserialize)]
 pub enum Spacing {
     /// Add a leading and trailing space around the widget contents
     Normal,
     /// Hide the leading space when the widget is inline
     Inline,
-    /// Hide both leading and trailing spaces when widget is hidden
+    /// Hide both leading and trailing spaces, e.g. to visually group widgets or when the widget
+    /// is hidden
     Hidden,
 }
 
-#[derive(Debug, Copy, Clone, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Deserialize)]
 pub enum State {
     Idle,
     Info,
@@ -63,6 +63,10 @@ impl TextWidget {
     }
 
     pub fn set_text(&mut self, content: String) {
+        // Skip re-rendering when nothing changed
+        if self.content.as_ref() == Some(&content) {
+            return;
+        }
         if content.is_empty() {
             self.spacing = Spacing::Hidden;
         }
@@ -83,10 +87,17 @@ impl TextWidget {
     fn update(&mut self) {
         let (key_bg, key_fg) = self.state.theme_keys(&self.shared_config.theme);
 
+        // A state icon only replaces an existing icon, so widgets without one stay without
+        let icon = self.icon.clone().map(|icon| {
+            self.shared_config
+                .get_state_icon(self.state)
+                .unwrap_or(icon)
+        });
+
         // When rendered inline, remove the leading space
         self.inner.full_text = format!(
             "{}{}{}",
-            self.icon.clone().unwrap_or_else(|| {
+            icon.unwrap_or_else(|| {
                 match self.spacing {
                     Spacing::Normal => String::from(" "),
                     _ => String::from(""),
@@ -108,3 +119,69 @@ impl I3BarWidget for TextWidget {
         self.inner.clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::config::{Config, SharedConfig};
+    use crate::widgets::{text::TextWidget, I3BarWidget, Spacing, State};
+
+    #[test]
+    fn test_spacing() {
+        let shared_config = SharedConfig::new(&Config::default());
+        for &(spacing, full_text) in [
+            (Spacing::Normal, " text "),
+            (Spacing::Inline, "text "),
+            (Spacing::Hidden, "text"),
+        ]
+        .iter()
+        {
+            let data = TextWidget::new(0, 0, shared_config.clone())
+                .with_text("text")
+                .with_spacing(spacing)
+                .get_data();
+            assert_eq!(data.full_text, full_text);
+            assert_eq!(data.separator_block_width, Some(0));
+        }
+    }
+
+    #[test]
+    fn test_set_text_changed() {
+        let mut widget = TextWidget::new(0, 0, SharedConfig::default());
+        widget.set_text("42%".to_string());
+        assert_eq!(widget.get_data().full_text, " 42% ");
+        widget.set_text("42%".to_string());
+        assert_eq!(widget.get_data().full_text, " 42% ");
+        widget.set_text("43%".to_string());
+        assert_eq!(widget.get_data().full_text, " 43% ");
+    }
+
+    #[test]
+    fn test_state_icons() {
+        let mut shared_config = SharedConfig::default();
+        shared_config
+            .state_icons_override(&map_to_owned!("critical" => "fan"))
+            .unwrap();
+
+        let mut widget = TextWidget::new(0, 0, shared_config.clone())
+            .with_icon("thermometer")
+            .with_text("90°");
+        assert_eq!(widget.get_data().full_text, " TEMP 90° ");
+        widget.set_state(State::Critical);
+        assert_eq!(widget.get_data().full_text, " FAN 90° ");
+        widget.set_state(State::Idle);
+        assert_eq!(widget.get_data().full_text, " TEMP 90° ");
+
+        // Widgets without an icon don't get one
+        let widget = TextWidget::new(0, 1, shared_config)
+            .with_text("90°")
+            .with_state(State::Critical);
+        assert_eq!(widget.get_data().full_text, " 90° ");
+
+        assert!(SharedConfig::default()
+            .state_icons_override(&map_to_owned!("hot" => "fan"))
+            .is_err());
+        assert!(SharedConfig::default()
+            .state_icons_override(&map_to_owned!("critical" => "fna"))
+            .is_err());
+    }
+}